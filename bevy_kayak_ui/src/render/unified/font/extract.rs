@@ -1,10 +1,14 @@
+use std::borrow::Cow;
+
 use bevy::{
+    asset::Handle,
     math::Vec2,
     prelude::{Assets, Res},
     sprite2::Rect,
 };
-use kayak_core::render_primitive::RenderPrimitive;
+use kayak_core::render_primitive::{Justify, RenderPrimitive, TextDirection, TextOverflow};
 use kayak_font::{Alignment, CoordinateSystem, KayakFont};
+use unicode_bidi::{BidiInfo, Level};
 
 use crate::{
     render::unified::pipeline::{ExtractQuadBundle, ExtractedQuad, UIQuadType},
@@ -13,24 +17,375 @@ use crate::{
 
 use super::font_mapping::FontMapping;
 
+const ELLIPSIS: char = '…';
+const ELLIPSIS_FALLBACK: &str = "...";
+
+/// Picks the single-character ellipsis glyph when the font has it, falling
+/// back to three literal dots otherwise.
+fn ellipsis_content(font: &KayakFont) -> &'static str {
+    if font.get_char_id(ELLIPSIS).is_some() {
+        "…"
+    } else {
+        ELLIPSIS_FALLBACK
+    }
+}
+
+/// Reorders one already-wrapped line of `content` into left-to-right visual
+/// order for `base_direction`, splitting it into bidi runs and placing them
+/// the way a line of Arabic, Hebrew, or mixed-direction text should read.
+///
+/// This must only be called on text that is known to be a single display
+/// line - per UAX #9, line-breaking has to happen on logical order first, so
+/// reordering a whole multi-line paragraph up front would wrap against
+/// scrambled text. [`layout_with_bidi`] is what enforces that ordering.
+///
+/// Note this does not perform bracket/punctuation mirroring (e.g. flipping
+/// `(` to `)` in an RTL run) - only run reordering.
+fn reorder_line_for_display(line: &str, base_direction: TextDirection) -> String {
+    let base_level = match base_direction {
+        TextDirection::Ltr => Some(Level::ltr()),
+        TextDirection::Rtl => Some(Level::rtl()),
+        TextDirection::Auto => None,
+    };
+
+    let bidi_info = BidiInfo::new(line, base_level);
+    let mut reordered = String::with_capacity(line.len());
+    for paragraph in &bidi_info.paragraphs {
+        reordered.push_str(&bidi_info.reorder_line(paragraph, paragraph.range.clone()));
+    }
+
+    reordered
+}
+
+#[cfg(test)]
+mod reorder_line_for_display_tests {
+    use super::*;
+
+    #[test]
+    fn plain_ltr_text_is_left_as_is() {
+        assert_eq!(
+            reorder_line_for_display("hello world", TextDirection::Ltr),
+            "hello world"
+        );
+    }
+
+    #[test]
+    fn an_rtl_base_direction_does_not_touch_pure_ltr_text() {
+        // No bidi characters to reorder, so run order is unaffected either way.
+        assert_eq!(
+            reorder_line_for_display("hello world", TextDirection::Rtl),
+            "hello world"
+        );
+    }
+}
+
+/// A single positioned glyph, detached from the font's own layout type so
+/// truncation can splice in a synthetic ellipsis glyph alongside real ones.
+#[derive(Clone, Copy)]
+struct Glyph {
+    position: Vec2,
+    size: Vec2,
+    content: char,
+}
+
+/// Lays out `content`, honoring bidi direction without letting reordering
+/// corrupt line wrapping: first wraps the untouched logical text to find
+/// where each display line actually breaks, then reorders and re-lays-out
+/// each line's glyphs independently so its wrap points stay correct.
+#[allow(clippy::too_many_arguments)]
+fn layout_with_bidi(
+    font: &KayakFont,
+    content: &str,
+    base_direction: TextDirection,
+    position: Vec2,
+    size: Vec2,
+    line_height: f32,
+    font_size: f32,
+) -> Vec<Glyph> {
+    let logical_layouts = font.get_layout(
+        CoordinateSystem::PositiveYDown,
+        Alignment::Start,
+        position,
+        size,
+        content,
+        line_height,
+        font_size,
+    );
+
+    let mut lines: Vec<Vec<usize>> = Vec::new();
+    for (index, char_layout) in logical_layouts.iter().enumerate() {
+        match lines.last() {
+            Some(line) if logical_layouts[line[0]].position.y == char_layout.position.y => {
+                lines.last_mut().unwrap().push(index);
+            }
+            _ => lines.push(vec![index]),
+        }
+    }
+
+    let mut glyphs = Vec::with_capacity(logical_layouts.len());
+    for line in &lines {
+        let line_content: String = line.iter().map(|&i| logical_layouts[i].content).collect();
+        let line_y = logical_layouts[line[0]].position.y;
+        let reordered_line = reorder_line_for_display(&line_content, base_direction);
+
+        let line_layouts = font.get_layout(
+            CoordinateSystem::PositiveYDown,
+            Alignment::Start,
+            Vec2::new(position.x, line_y),
+            Vec2::new(f32::MAX, font_size),
+            &reordered_line,
+            line_height,
+            font_size,
+        );
+
+        glyphs.extend(line_layouts.into_iter().map(|char_layout| Glyph {
+            position: char_layout.position,
+            size: char_layout.size,
+            content: char_layout.content,
+        }));
+    }
+
+    glyphs
+}
+
+/// Finds where to truncate `line_glyphs` to make room for the ellipsis, or
+/// `None` if the line already fits and doesn't need truncating at all.
+///
+/// Truncation always trims the *end* of the line in reading order, not
+/// necessarily the visual-right edge: for LTR (and auto-resolved) text those
+/// are the same thing, so the returned index marks where to `truncate` and
+/// append the ellipsis after. RTL text reads right-to-left, so its reading
+/// end is the visual-left edge instead - the returned index then marks the
+/// last glyph counting in from the visual left to drop, with the ellipsis
+/// placed just to the left of what's kept.
+fn ellipsis_cutoff(
+    line_glyphs: &[Glyph],
+    line_left: f32,
+    line_right: f32,
+    ellipsis_width: f32,
+    max_width: f32,
+    base_direction: TextDirection,
+) -> Option<usize> {
+    if line_right - line_left <= max_width {
+        return None;
+    }
+
+    if matches!(base_direction, TextDirection::Rtl) {
+        line_glyphs
+            .iter()
+            .rposition(|glyph| line_right - glyph.position.x + ellipsis_width > max_width)
+    } else {
+        line_glyphs.iter().position(|glyph| {
+            glyph.position.x + glyph.size.x - line_left + ellipsis_width > max_width
+        })
+    }
+}
+
+#[cfg(test)]
+mod ellipsis_cutoff_tests {
+    use super::*;
+
+    fn glyph(x: f32) -> Glyph {
+        Glyph {
+            position: Vec2::new(x, 0.0),
+            size: Vec2::new(10.0, 12.0),
+            content: 'a',
+        }
+    }
+
+    #[test]
+    fn a_line_that_already_fits_is_left_alone() {
+        // Three 10-wide glyphs starting at 0 span exactly 30, the max width.
+        let line = [glyph(0.0), glyph(10.0), glyph(20.0)];
+        assert_eq!(
+            ellipsis_cutoff(&line, 0.0, 30.0, 5.0, 30.0, TextDirection::Ltr),
+            None
+        );
+    }
+
+    #[test]
+    fn an_overflowing_ltr_line_is_truncated_from_the_visual_right() {
+        // Four 10-wide glyphs span 40 against a max width of 30.
+        let line = [glyph(0.0), glyph(10.0), glyph(20.0), glyph(30.0)];
+        let cutoff = ellipsis_cutoff(&line, 0.0, 40.0, 5.0, 30.0, TextDirection::Ltr);
+        assert_eq!(cutoff, Some(2));
+    }
+
+    #[test]
+    fn an_overflowing_rtl_line_is_truncated_from_the_visual_left() {
+        // Same four glyphs, but RTL reading starts at the visual-right edge,
+        // so the glyphs nearest x=30 are kept and the ones nearest x=0 are
+        // dropped instead.
+        let line = [glyph(0.0), glyph(10.0), glyph(20.0), glyph(30.0)];
+        let cutoff = ellipsis_cutoff(&line, 0.0, 40.0, 5.0, 30.0, TextDirection::Rtl);
+        assert_eq!(cutoff, Some(1));
+    }
+}
+
+/// Extents of a laid-out string, computed without emitting any quads.
+///
+/// Widgets use this to size themselves to their text, position cursors, and
+/// implement scroll regions before the render-extract stage ever runs.
+pub struct TextMetrics {
+    pub width: f32,
+    pub height: f32,
+    pub line_count: usize,
+    pub ascent: f32,
+    pub descent: f32,
+    pub baseline: f32,
+}
+
+/// Sums up the width, height, and line count of an already-positioned glyph
+/// run. Split out from [`measure_text`] so the aggregation itself - the part
+/// that doesn't need a real font - can be unit tested directly.
+fn aggregate_metrics(glyphs: &[Glyph], ascent: f32, descent: f32) -> TextMetrics {
+    let mut width = 0.0_f32;
+    let mut height = 0.0_f32;
+    let mut line_count = 0;
+    let mut last_line_y = None;
+
+    for glyph in glyphs {
+        width = width.max(glyph.position.x + glyph.size.x);
+        height = height.max(glyph.position.y + glyph.size.y);
+
+        if last_line_y != Some(glyph.position.y) {
+            line_count += 1;
+            last_line_y = Some(glyph.position.y);
+        }
+    }
+
+    TextMetrics {
+        width,
+        height,
+        line_count,
+        ascent,
+        descent,
+        baseline: ascent,
+    }
+}
+
+/// Measures `content` with the same [`KayakFont::get_layout`] wrapping pass
+/// that [`extract_texts`] starts from, but stops short of building
+/// [`ExtractedQuad`]s. The metrics match the rendered line breaks when
+/// `line_height` is the 1.2x font size that [`extract_texts`] uses; the
+/// justification and ellipsis truncation that [`extract_texts`] applies
+/// afterwards are not reflected. `KayakFont` doesn't expose real face
+/// ascent/descent, so those (and the baseline derived from them) are
+/// approximated the same way layout itself does: ascent as the font size,
+/// descent as whatever's left of the line height.
+pub fn measure_text(
+    font: &KayakFont,
+    content: &str,
+    font_size: f32,
+    line_height: f32,
+    max_width: f32,
+) -> TextMetrics {
+    let chars_layouts = font.get_layout(
+        CoordinateSystem::PositiveYDown,
+        Alignment::Start,
+        Vec2::new(0.0, font_size),
+        Vec2::new(max_width, f32::MAX),
+        content,
+        line_height,
+        font_size,
+    );
+
+    let glyphs: Vec<Glyph> = chars_layouts
+        .into_iter()
+        .map(|char_layout| Glyph {
+            position: char_layout.position,
+            size: char_layout.size,
+            content: char_layout.content,
+        })
+        .collect();
+
+    aggregate_metrics(&glyphs, font_size, line_height - font_size)
+}
+
+#[cfg(test)]
+mod measure_text_tests {
+    use super::*;
+
+    fn glyph(x: f32, y: f32, w: f32, h: f32) -> Glyph {
+        Glyph {
+            position: Vec2::new(x, y),
+            size: Vec2::new(w, h),
+            content: 'a',
+        }
+    }
+
+    #[test]
+    fn empty_run_has_no_extents() {
+        let metrics = aggregate_metrics(&[], 10.0, 3.0);
+        assert_eq!(metrics.width, 0.0);
+        assert_eq!(metrics.height, 0.0);
+        assert_eq!(metrics.line_count, 0);
+    }
+
+    #[test]
+    fn width_and_height_track_the_furthest_glyph_extent() {
+        let glyphs = [glyph(0.0, 0.0, 10.0, 12.0), glyph(10.0, 0.0, 8.0, 12.0)];
+        let metrics = aggregate_metrics(&glyphs, 10.0, 3.0);
+        assert_eq!(metrics.width, 18.0);
+        assert_eq!(metrics.height, 12.0);
+    }
+
+    #[test]
+    fn glyphs_on_a_new_baseline_count_as_a_new_line() {
+        let glyphs = [
+            glyph(0.0, 0.0, 10.0, 12.0),
+            glyph(10.0, 0.0, 10.0, 12.0),
+            glyph(0.0, 12.0, 10.0, 12.0),
+        ];
+        let metrics = aggregate_metrics(&glyphs, 10.0, 3.0);
+        assert_eq!(metrics.line_count, 2);
+    }
+
+    #[test]
+    fn ascent_descent_and_baseline_come_from_the_caller_supplied_font_metrics() {
+        let metrics = aggregate_metrics(&[], 11.0, 4.0);
+        assert_eq!(metrics.ascent, 11.0);
+        assert_eq!(metrics.descent, 4.0);
+        assert_eq!(metrics.baseline, 11.0);
+    }
+}
+
 pub fn extract_texts(
     render_primitive: &RenderPrimitive,
     fonts: &Res<Assets<KayakFont>>,
     font_mapping: &Res<FontMapping>,
 ) -> Vec<ExtractQuadBundle> {
     let mut extracted_texts = Vec::new();
-    let (background_color, layout, font_size, content, font) = match render_primitive {
-        RenderPrimitive::Text {
-            color,
-            layout,
-            size,
-            content,
-            font,
-        } => (color, layout, *size, content, *font),
-        _ => panic!(""),
-    };
+    let (background_color, layout, font_size, content, font, justify, overflow, base_direction) =
+        match render_primitive {
+            RenderPrimitive::Text {
+                color,
+                layout,
+                size,
+                content,
+                font,
+                justify,
+                overflow,
+                base_direction,
+            } => (
+                color,
+                layout,
+                *size,
+                content,
+                *font,
+                *justify,
+                *overflow,
+                *base_direction,
+            ),
+            _ => panic!(""),
+        };
 
-    let font_handle = font_mapping.get_handle(font).unwrap();
+    // An unrecognized font key (e.g. a system font that hasn't resolved yet)
+    // is no different from a handle whose asset hasn't loaded yet below:
+    // skip this frame's quads and pick it up once it's ready.
+    let Some(font_handle) = font_mapping.get_handle(font) else {
+        return vec![];
+    };
     let font = fonts.get(font_handle.clone());
 
     if font.is_none() {
@@ -39,36 +394,354 @@ pub fn extract_texts(
 
     let font = font.unwrap();
 
-    let chars_layouts = font.get_layout(
-        CoordinateSystem::PositiveYDown,
-        Alignment::Start,
+    let chars_layouts = layout_with_bidi(
+        font,
+        content,
+        base_direction,
         Vec2::new(layout.posx, layout.posy + font_size),
         Vec2::new(layout.width, layout.height),
-        content,
         font_size * 1.2,
         font_size,
     );
 
-    for char_layout in chars_layouts {
-        extracted_texts.push(ExtractQuadBundle {
-            extracted_quad: ExtractedQuad {
-                font_handle: Some(font_handle.clone()),
-                rect: Rect {
-                    min: char_layout.position,
-                    max: char_layout.position + char_layout.size,
+    // Glyphs on the same visual line share a baseline y, so group by that to
+    // justify (and, if needed, truncate) each line independently rather than
+    // the whole block at once.
+    let mut lines: Vec<Vec<usize>> = Vec::new();
+    for (index, char_layout) in chars_layouts.iter().enumerate() {
+        match lines.last() {
+            Some(line) if chars_layouts[line[0]].position.y == char_layout.position.y => {
+                lines.last_mut().unwrap().push(index);
+            }
+            _ => lines.push(vec![index]),
+        }
+    }
+
+    let ellipsis_glyphs: Vec<Glyph> = if matches!(overflow, TextOverflow::Ellipsis) {
+        font.get_layout(
+            CoordinateSystem::PositiveYDown,
+            Alignment::Start,
+            Vec2::ZERO,
+            Vec2::new(f32::MAX, f32::MAX),
+            ellipsis_content(font),
+            font_size * 1.2,
+            font_size,
+        )
+        .into_iter()
+        .map(|glyph| Glyph {
+            position: glyph.position,
+            size: glyph.size,
+            content: glyph.content,
+        })
+        .collect()
+    } else {
+        Vec::new()
+    };
+    let ellipsis_width = ellipsis_glyphs
+        .iter()
+        .map(|glyph| glyph.position.x + glyph.size.x)
+        .fold(0.0_f32, f32::max);
+
+    for line in &lines {
+        let mut line_glyphs: Vec<Glyph> = line
+            .iter()
+            .map(|&i| Glyph {
+                position: chars_layouts[i].position,
+                size: chars_layouts[i].size,
+                content: chars_layouts[i].content,
+            })
+            .collect();
+
+        let line_left = line_glyphs[0].position.x;
+        let line_y = line_glyphs[0].position.y;
+        let line_right_full = line_glyphs
+            .iter()
+            .map(|glyph| glyph.position.x + glyph.size.x)
+            .fold(line_left, f32::max);
+
+        if matches!(overflow, TextOverflow::Ellipsis) {
+            let cutoff = ellipsis_cutoff(
+                &line_glyphs,
+                line_left,
+                line_right_full,
+                ellipsis_width,
+                layout.width,
+                base_direction,
+            );
+
+            if let Some(cutoff) = cutoff {
+                if matches!(base_direction, TextDirection::Rtl) {
+                    // RTL reads right-to-left, so the head we keep is the
+                    // tail of `line_glyphs` (the visual-right glyphs) and the
+                    // ellipsis sits just to the left of it, not at the
+                    // line's right edge like the LTR case below.
+                    let truncate_x = line_glyphs[cutoff + 1].position.x;
+                    let kept = line_glyphs.split_off(cutoff + 1);
+                    line_glyphs = ellipsis_glyphs
+                        .iter()
+                        .map(|glyph| Glyph {
+                            position: Vec2::new(
+                                truncate_x - ellipsis_width + glyph.position.x,
+                                line_y,
+                            ),
+                            size: glyph.size,
+                            content: glyph.content,
+                        })
+                        .chain(kept)
+                        .collect();
+                } else {
+                    let truncate_x = match cutoff {
+                        0 => line_left,
+                        _ => {
+                            let last = line_glyphs[cutoff - 1];
+                            last.position.x + last.size.x
+                        }
+                    };
+
+                    line_glyphs.truncate(cutoff);
+                    line_glyphs.extend(ellipsis_glyphs.iter().map(|glyph| Glyph {
+                        position: Vec2::new(truncate_x + glyph.position.x, line_y),
+                        size: glyph.size,
+                        content: glyph.content,
+                    }));
+                }
+            }
+        }
+
+        let line_right = line_glyphs
+            .iter()
+            .map(|glyph| glyph.position.x + glyph.size.x)
+            .fold(line_left, f32::max);
+        let line_width = line_right - line_left;
+
+        let h_anchor = match justify {
+            Justify::Left => 0.0,
+            Justify::Center => (layout.width - line_width) * 0.5,
+            Justify::Right => layout.width - line_width,
+        }
+        .floor();
+
+        for glyph in &line_glyphs {
+            let position = Vec2::new(glyph.position.x + h_anchor, glyph.position.y);
+            let rect = Rect {
+                min: position,
+                max: position + glyph.size,
+            };
+
+            let fully_clipped = rect.max.x <= layout.posx
+                || rect.min.x >= layout.posx + layout.width
+                || rect.max.y <= layout.posy
+                || rect.min.y >= layout.posy + layout.height;
+
+            if matches!(overflow, TextOverflow::Clip) && fully_clipped {
+                continue;
+            }
+
+            extracted_texts.push(ExtractQuadBundle {
+                extracted_quad: ExtractedQuad {
+                    font_handle: Some(font_handle.clone()),
+                    rect,
+                    color: to_bevy_color(background_color),
+                    vertex_index: 0,
+                    char_id: font.get_char_id(glyph.content).unwrap(),
+                    z_index: layout.z_index,
+                    quad_type: UIQuadType::Text,
+                    type_index: 0,
+                    border_radius: (0.0, 0.0, 0.0, 0.0),
+                    image: None,
+                    uv_max: None,
+                    uv_min: None,
                 },
-                color: to_bevy_color(background_color),
-                vertex_index: 0,
+            });
+        }
+    }
+
+    extracted_texts
+}
+
+/// Like [`Glyph`], but each one remembers which span it came from so a run
+/// of rich text can carry its own color and font handle into the quad.
+#[derive(Clone)]
+struct RichGlyph {
+    position: Vec2,
+    size: Vec2,
+    char_id: u16,
+    color: kayak_core::Color,
+    font_handle: Handle<KayakFont>,
+    line_index: usize,
+}
+
+/// Extracts quads for [`RenderPrimitive::TextSpans`], the rich-text sibling
+/// of [`extract_texts`]. Spans are laid out back to back on shared
+/// baselines - starting a new span never starts a new line; only a '\n' in
+/// a span's content does - and every glyph's quad carries its own span's color and
+/// font handle instead of one color/font for the whole primitive.
+pub fn extract_rich_texts(
+    render_primitive: &RenderPrimitive,
+    fonts: &Res<Assets<KayakFont>>,
+    font_mapping: &Res<FontMapping>,
+) -> Vec<ExtractQuadBundle> {
+    let (layout, justify, base_direction, spans) = match render_primitive {
+        RenderPrimitive::TextSpans {
+            layout,
+            justify,
+            base_direction,
+            spans,
+        } => (layout, *justify, *base_direction, spans),
+        _ => panic!(""),
+    };
+
+    // A span only ever breaks the line at an explicit '\n' in its own
+    // content - it never wraps on its own - so the line structure can be
+    // determined up front by splitting each span on '\n' rather than by
+    // comparing get_layout's output positions, which would disagree across
+    // spans of different sizes. `line_index` groups these segments, and
+    // `max_size_by_line` tracks the largest span size feeding each line so
+    // every span on a line shares one baseline instead of each computing its
+    // own from `cursor_y + span.size`.
+    struct Segment<'a> {
+        line_index: usize,
+        font: &'static str,
+        size: f32,
+        color: kayak_core::Color,
+        content: Cow<'a, str>,
+    }
+
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut max_size_by_line: Vec<f32> = vec![0.0];
+    let mut line_index = 0usize;
+
+    for span in spans {
+        for (part_index, part) in span.content.split('\n').enumerate() {
+            if part_index > 0 {
+                line_index += 1;
+                max_size_by_line.push(0.0);
+            }
+
+            max_size_by_line[line_index] = max_size_by_line[line_index].max(span.size);
+            segments.push(Segment {
+                line_index,
+                font: span.font,
+                size: span.size,
+                color: span.color,
+                content: Cow::Borrowed(part),
+            });
+        }
+    }
+
+    let mut line_baselines = Vec::with_capacity(max_size_by_line.len());
+    let mut line_top = layout.posy;
+    for (index, &max_size) in max_size_by_line.iter().enumerate() {
+        if index > 0 {
+            line_top += max_size_by_line[index - 1] * 1.2;
+        }
+        line_baselines.push(line_top + max_size);
+    }
+
+    let mut glyphs: Vec<RichGlyph> = Vec::new();
+    let mut cursor_x = layout.posx;
+    let mut current_line = 0usize;
+
+    for segment in &segments {
+        if segment.line_index != current_line {
+            cursor_x = layout.posx;
+            current_line = segment.line_index;
+        }
+
+        let Some(font_handle) = font_mapping.get_handle(segment.font) else {
+            continue;
+        };
+        let Some(font) = fonts.get(font_handle.clone()) else {
+            continue;
+        };
+
+        // The line has already been split on '\n' above, so this segment is
+        // always a single line - an unbounded width just stops get_layout
+        // from wrapping it on its own.
+        let content = reorder_line_for_display(&segment.content, base_direction);
+        let span_layouts = font.get_layout(
+            CoordinateSystem::PositiveYDown,
+            Alignment::Start,
+            Vec2::new(cursor_x, line_baselines[segment.line_index]),
+            Vec2::new(f32::MAX, layout.height),
+            &content,
+            segment.size * 1.2,
+            segment.size,
+        );
+
+        for char_layout in &span_layouts {
+            glyphs.push(RichGlyph {
+                position: char_layout.position,
+                size: char_layout.size,
                 char_id: font.get_char_id(char_layout.content).unwrap(),
-                z_index: layout.z_index,
-                quad_type: UIQuadType::Text,
-                type_index: 0,
-                border_radius: (0.0, 0.0, 0.0, 0.0),
-                image: None,
-                uv_max: None,
-                uv_min: None,
-            },
-        });
+                color: segment.color,
+                font_handle: font_handle.clone(),
+                line_index: segment.line_index,
+            });
+        }
+
+        // Continue the next segment from where this one's last glyph ended,
+        // so runs on the same line read continuously instead of each
+        // restarting at the layout rect's left edge.
+        if let Some(last) = span_layouts.last() {
+            cursor_x = last.position.x + last.size.x;
+        }
+    }
+
+    // Group glyphs by the line index their segment was assigned above to
+    // justify each line independently rather than the whole block at once,
+    // since a span's embedded newline can still split this primitive across
+    // more than one line.
+    let mut lines: Vec<Vec<usize>> = Vec::new();
+    for (index, glyph) in glyphs.iter().enumerate() {
+        match lines.last() {
+            Some(line) if glyphs[line[0]].line_index == glyph.line_index => {
+                lines.last_mut().unwrap().push(index);
+            }
+            _ => lines.push(vec![index]),
+        }
+    }
+
+    let mut extracted_texts = Vec::new();
+    for line in &lines {
+        let line_left = glyphs[line[0]].position.x;
+        let line_right = line
+            .iter()
+            .map(|&i| glyphs[i].position.x + glyphs[i].size.x)
+            .fold(line_left, f32::max);
+        let line_width = line_right - line_left;
+
+        let h_anchor = match justify {
+            Justify::Left => 0.0,
+            Justify::Center => (layout.width - line_width) * 0.5,
+            Justify::Right => layout.width - line_width,
+        }
+        .floor();
+
+        for &index in line {
+            let glyph = &glyphs[index];
+            let position = Vec2::new(glyph.position.x + h_anchor, glyph.position.y);
+
+            extracted_texts.push(ExtractQuadBundle {
+                extracted_quad: ExtractedQuad {
+                    font_handle: Some(glyph.font_handle.clone()),
+                    rect: Rect {
+                        min: position,
+                        max: position + glyph.size,
+                    },
+                    color: to_bevy_color(&glyph.color),
+                    vertex_index: 0,
+                    char_id: glyph.char_id,
+                    z_index: layout.z_index,
+                    quad_type: UIQuadType::Text,
+                    type_index: 0,
+                    border_radius: (0.0, 0.0, 0.0, 0.0),
+                    image: None,
+                    uv_max: None,
+                    uv_min: None,
+                },
+            });
+        }
     }
 
     extracted_texts
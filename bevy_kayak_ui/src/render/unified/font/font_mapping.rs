@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use bevy::asset::{Assets, Handle};
+use kayak_font::KayakFont;
+
+use super::system_fonts::{platform_source, SystemFontStyle};
+
+/// Resolves font keys - explicit asset labels like `"roboto"`, or a bare
+/// family name like `"sans-serif"` / `"Arial Bold"` - to a loaded
+/// [`KayakFont`] handle.
+///
+/// Explicitly registered handles are the fast default path and are checked
+/// first. A key with no registered handle is assumed to be a system font
+/// family and is queued for the platform backend to resolve; once loaded it
+/// is registered the same way, so later lookups hit the fast path too. A key
+/// the backend fails to resolve is remembered as failed rather than requeued,
+/// since a lookup that misses once (e.g. the family genuinely isn't
+/// installed, or this platform's backend is a stub - see
+/// [`super::system_fonts`]) will keep missing on every later frame too.
+pub struct FontMapping {
+    handles: HashMap<&'static str, Handle<KayakFont>>,
+    pending: Mutex<HashSet<&'static str>>,
+    failed: Mutex<HashSet<&'static str>>,
+    system_fonts: Box<dyn super::system_fonts::SystemFontSource>,
+}
+
+impl Default for FontMapping {
+    fn default() -> Self {
+        Self {
+            handles: HashMap::new(),
+            pending: Mutex::new(HashSet::new()),
+            failed: Mutex::new(HashSet::new()),
+            system_fonts: platform_source(),
+        }
+    }
+}
+
+impl FontMapping {
+    /// Registers an explicitly loaded font under `key`.
+    pub fn add(&mut self, key: &'static str, handle: &Handle<KayakFont>) {
+        self.handles.insert(key, handle.clone());
+        self.pending.lock().unwrap().remove(key);
+        self.failed.lock().unwrap().remove(key);
+    }
+
+    /// Returns the handle for `key`, if it has been registered. If not, and
+    /// `key` hasn't already failed to resolve, it is queued as a system font
+    /// family for [`FontMapping::resolve_pending_system_fonts`] to load on a
+    /// later pass.
+    pub fn get_handle(&self, key: &'static str) -> Option<Handle<KayakFont>> {
+        if let Some(handle) = self.handles.get(key) {
+            return Some(handle.clone());
+        }
+
+        if self.failed.lock().unwrap().contains(key) {
+            return None;
+        }
+
+        self.pending.lock().unwrap().insert(key);
+        None
+    }
+
+    /// Loads any system fonts requested through [`FontMapping::get_handle`]
+    /// since the last call, registering each one so it resolves through the
+    /// fast path from then on. Run this from a system with mutable access to
+    /// `Assets<KayakFont>`.
+    pub fn resolve_pending_system_fonts(&mut self, fonts: &mut Assets<KayakFont>) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+
+        for key in pending {
+            let (family, style) = SystemFontStyle::parse(key);
+            let Some(bytes) = self.system_fonts.find(family, style) else {
+                // A lookup that fails once will keep failing without a
+                // restart (the family isn't installed, or this platform has
+                // no real backend yet) - cache the miss so it isn't retried
+                // every frame forever.
+                self.failed.lock().unwrap().insert(key);
+                continue;
+            };
+
+            let handle = fonts.add(KayakFont::from_bytes(bytes));
+            self.handles.insert(key, handle);
+        }
+    }
+}
+
+/// System that drains [`FontMapping`]'s pending system-font requests each
+/// frame, keeping the resolution off the extraction hot path.
+pub fn resolve_system_fonts(
+    mut font_mapping: bevy::prelude::ResMut<FontMapping>,
+    mut fonts: bevy::prelude::ResMut<Assets<KayakFont>>,
+) {
+    font_mapping.resolve_pending_system_fonts(&mut fonts);
+}
+
+/// Registers [`FontMapping`] and its [`resolve_system_fonts`] system on
+/// `app`. Without this, system fonts are queued by [`FontMapping::get_handle`]
+/// but nothing ever drains the queue.
+pub fn add_font_mapping_systems(app: &mut bevy::prelude::App) {
+    app.init_resource::<FontMapping>()
+        .add_system(resolve_system_fonts);
+}
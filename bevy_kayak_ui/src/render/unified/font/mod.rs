@@ -0,0 +1,6 @@
+pub mod extract;
+pub mod font_mapping;
+mod system_fonts;
+
+pub use extract::{extract_rich_texts, extract_texts};
+pub use font_mapping::{add_font_mapping_systems, resolve_system_fonts, FontMapping};
@@ -0,0 +1,144 @@
+//! Platform backends for resolving a font family name to raw font bytes.
+//!
+//! Each OS exposes its own font enumeration API - DirectWrite, CoreText, or
+//! FontConfig/FreeType - so the lookup is hidden behind [`SystemFontSource`]
+//! and [`FontMapping`](super::font_mapping::FontMapping) only ever talks to
+//! that trait.
+
+/// Weight and slant to match when searching installed fonts, mirroring the
+/// subset of CSS-style font properties every platform backend understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemFontStyle {
+    pub weight: u16,
+    pub italic: bool,
+}
+
+impl Default for SystemFontStyle {
+    fn default() -> Self {
+        Self {
+            weight: 400,
+            italic: false,
+        }
+    }
+}
+
+impl SystemFontStyle {
+    /// Parses trailing style keywords off a family name, e.g. "Arial Bold
+    /// Italic" becomes the family "Arial" with a bold, italic style.
+    pub fn parse(name: &str) -> (&str, Self) {
+        let mut family = name;
+        let mut style = Self::default();
+
+        if let Some(stripped) = family.strip_suffix(" Italic") {
+            style.italic = true;
+            family = stripped;
+        }
+        if let Some(stripped) = family.strip_suffix(" Bold") {
+            style.weight = 700;
+            family = stripped;
+        }
+
+        (family, style)
+    }
+}
+
+/// A source of installed, unbundled fonts, keyed by family name.
+pub trait SystemFontSource: Send + Sync {
+    /// Looks up `family`/`style` among the fonts installed on this machine
+    /// and returns its raw font file bytes, if found.
+    fn find(&self, family: &str, style: SystemFontStyle) -> Option<Vec<u8>>;
+}
+
+/// TODO: unimplemented. DirectWrite font enumeration (`IDWriteFontCollection`)
+/// is not wired up yet, so every lookup misses, the same as [`NullFontSource`]
+/// - Windows system-font resolution is a known, tracked gap, not a silently
+/// working backend. [`FontMapping`](super::font_mapping::FontMapping)'s
+/// negative caching means a miss here is only paid once per family rather
+/// than every frame.
+#[cfg(target_os = "windows")]
+pub struct DirectWriteFontSource;
+
+#[cfg(target_os = "windows")]
+impl SystemFontSource for DirectWriteFontSource {
+    fn find(&self, _family: &str, _style: SystemFontStyle) -> Option<Vec<u8>> {
+        // Enumerate via IDWriteFontCollection and read the matching face's
+        // backing file into memory.
+        None
+    }
+}
+
+/// TODO: unimplemented. CoreText font enumeration
+/// (`CTFontCollectionCreateMatchingFontDescriptors`) is not wired up yet, so
+/// every lookup misses, the same as [`NullFontSource`] - macOS system-font
+/// resolution is a known, tracked gap, not a silently working backend.
+/// [`FontMapping`](super::font_mapping::FontMapping)'s negative caching means
+/// a miss here is only paid once per family rather than every frame.
+#[cfg(target_os = "macos")]
+pub struct CoreTextFontSource;
+
+#[cfg(target_os = "macos")]
+impl SystemFontSource for CoreTextFontSource {
+    fn find(&self, _family: &str, _style: SystemFontStyle) -> Option<Vec<u8>> {
+        // Resolve with CTFontCollectionCreateMatchingFontDescriptors and read
+        // the matching CTFont's file URL.
+        None
+    }
+}
+
+/// Resolves fonts through the system's fontconfig database, reading the
+/// matched face's backing file off disk. This is the one backend that's
+/// actually wired up end to end; Windows and macOS are left as stubs above
+/// until someone needs them.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub struct FontconfigFontSource {
+    fontconfig: fontconfig::Fontconfig,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl FontconfigFontSource {
+    pub fn new() -> Option<Self> {
+        Some(Self {
+            fontconfig: fontconfig::Fontconfig::new()?,
+        })
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl SystemFontSource for FontconfigFontSource {
+    fn find(&self, family: &str, style: SystemFontStyle) -> Option<Vec<u8>> {
+        let style_name = match (style.weight >= 700, style.italic) {
+            (true, true) => Some("Bold Italic"),
+            (true, false) => Some("Bold"),
+            (false, true) => Some("Italic"),
+            (false, false) => None,
+        };
+
+        let font = self.fontconfig.find(family, style_name)?;
+        std::fs::read(font.path).ok()
+    }
+}
+
+/// Backend used when no platform font source is available (e.g. fontconfig
+/// failed to initialize). Always misses, same as looking a font up before
+/// any backend existed.
+pub struct NullFontSource;
+
+impl SystemFontSource for NullFontSource {
+    fn find(&self, _family: &str, _style: SystemFontStyle) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Builds the [`SystemFontSource`] for the current platform.
+pub fn platform_source() -> Box<dyn SystemFontSource> {
+    #[cfg(target_os = "windows")]
+    return Box::new(DirectWriteFontSource);
+
+    #[cfg(target_os = "macos")]
+    return Box::new(CoreTextFontSource);
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    return FontconfigFontSource::new()
+        .map(|source| Box::new(source) as Box<dyn SystemFontSource>)
+        .unwrap_or_else(|| Box::new(NullFontSource));
+}